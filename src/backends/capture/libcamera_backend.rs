@@ -0,0 +1,593 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    ApiBackend, Buffer, CameraControl, CameraFormat, CameraInfo, CaptureBackendTrait,
+    ControlValueSetter, FrameFormat, KnownCameraControl, NokhwaError, Resolution,
+};
+use drm_fourcc::DrmFourcc;
+use libcamera::{
+    camera::{ActiveCamera, CameraConfigurationStatus},
+    camera_manager::CameraManager,
+    framebuffer::AsFrameBuffer,
+    framebuffer_allocator::{FrameBuffer, FrameBufferAllocator},
+    framebuffer_map::MemoryMappedFrameBuffer,
+    geometry::Size,
+    pixel_format::PixelFormat,
+    request::{Request, ReuseFlag},
+    stream::StreamRole,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Returns the process-wide `libcamera` [`CameraManager`], creating it on first use.
+///
+/// `libcamera`'s documented lifecycle is one manager per process, shared by every camera handle -
+/// constructing more than one is unsupported and would mean two managers enumerating hardware at
+/// once. Every [`LibCameraCaptureDevice`] borrows this single, lazily-initialized instance rather
+/// than creating (and leaking) its own.
+/// # Errors
+/// If the `libcamera` manager fails to initialize.
+fn camera_manager() -> Result<&'static CameraManager, NokhwaError> {
+    static MANAGER: OnceLock<CameraManager> = OnceLock::new();
+    if let Some(manager) = MANAGER.get() {
+        return Ok(manager);
+    }
+    let manager = CameraManager::new().map_err(|why| NokhwaError::InitializeError {
+        backend: ApiBackend::LibCamera,
+        error: why.to_string(),
+    })?;
+    Ok(MANAGER.get_or_init(|| manager))
+}
+
+/// Maps a [`FrameFormat`] to the `libcamera` pixel format requested during stream negotiation.
+///
+/// Only formats [`stitch_planes`](LibCameraCaptureDevice::stitch_planes) can correctly reassemble
+/// are supported: [`FrameFormat::NV12`] and [`FrameFormat::GrayU8`] are both tightly-packed,
+/// 1-byte-per-sample planar layouts, so `row_bytes` (a byte count, not a sample count) matches
+/// their frame width exactly. [`FrameFormat::YUYV`] and [`FrameFormat::GrayU16`] pack 2 bytes per
+/// sample, which `row_bytes = width.min(stride)` would silently truncate to half their real row
+/// width, and [`FrameFormat::MJPEG`] is a compressed, non-planar stream with no fixed row stride
+/// at all, so none of the three are offered.
+/// # Errors
+/// Returns [`NokhwaError::SetPropertyError`] if `format` is not one of the supported formats.
+fn pixel_format_for(format: FrameFormat) -> Result<PixelFormat, NokhwaError> {
+    let fourcc = match format {
+        FrameFormat::NV12 => DrmFourcc::Nv12,
+        FrameFormat::GrayU8 => DrmFourcc::R8,
+        FrameFormat::MJPEG | FrameFormat::YUYV | FrameFormat::GrayU16 => {
+            return Err(NokhwaError::SetPropertyError {
+                property: "FrameFormat".to_string(),
+                value: format.to_string(),
+                error: "libcamera backend only supports NV12 and GrayU8, whose planes \
+                        stitch_planes can stitch without accounting for multi-byte samples or \
+                        compressed, non-planar data"
+                    .to_string(),
+            })
+        }
+    };
+    Ok(PixelFormat::new(fourcc as u32, 0))
+}
+
+/// Describes one plane of a multi-planar libcamera frame buffer: the number of rows it holds, and
+/// the real row layout within that plane's own mapped slice. A single frame (e.g. NV12) may be
+/// delivered as several of these rather than one contiguous tightly-packed buffer, so `frame_raw`
+/// must stitch them back together using this information instead of assuming a single plane.
+///
+/// `MemoryMappedFrameBuffer::data()` returns one slice per plane, each already starting at that
+/// plane's own offset, so no offset needs to be recorded or re-applied here.
+#[derive(Clone, Copy, Debug)]
+struct PlaneLayout {
+    /// Number of rows in this plane.
+    rows: usize,
+    /// Number of bytes of real pixel data per row, not counting any stride padding.
+    row_bytes: usize,
+    /// Number of bytes libcamera advances between rows in the mapped buffer. May exceed
+    /// `row_bytes` when the hardware pads rows out to an alignment boundary.
+    stride: usize,
+}
+
+/// A struct that supports modern Linux cameras via `libcamera` directly, rather than going through
+/// V4L2/OpenCV.
+///
+/// Unlike the V4L2 and `OpenCV` backends, a `libcamera` frame commonly arrives as several
+/// separate plane buffers (e.g. a Y plane and an interleaved UV plane for NV12), each with its
+/// own stride and offset. [`CaptureBackendTrait::frame_raw`] stitches these planes into a single
+/// tightly-packed buffer using [`PlaneLayout`] rather than assuming one contiguous allocation.
+///
+/// `camera` and `requests` borrow from the process-wide [`camera_manager()`], which is shared
+/// across every [`LibCameraCaptureDevice`] and lives for the lifetime of the process, so the
+/// `'static` lifetime on `camera` never outlives anything it borrows from.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-libcamera")))]
+pub struct LibCameraCaptureDevice {
+    camera_info: CameraInfo,
+    camera_format: CameraFormat,
+    camera: ActiveCamera<'static>,
+    allocator: FrameBufferAllocator,
+    planes: Vec<PlaneLayout>,
+    /// Requests with a buffer already attached, waiting to be queued once the stream starts.
+    /// `frame_raw` re-queues each one as it completes, so the camera always has buffers in
+    /// flight for `wait_for_request` to wait on.
+    requests: Vec<Request<'static>>,
+    stream_open: bool,
+}
+
+impl LibCameraCaptureDevice {
+    /// Creates a new [`LibCameraCaptureDevice`] from a camera index.
+    /// # Errors
+    /// If the `libcamera` manager fails to enumerate cameras, the index is out of range, or the
+    /// camera fails to be acquired, this will error.
+    pub fn new(index: usize) -> Result<Self, NokhwaError> {
+        let manager = camera_manager()?;
+        let cameras = manager.cameras();
+        let camera_entry = cameras.get(index).ok_or_else(|| {
+            NokhwaError::OpenDeviceError(index.to_string(), "No such camera".to_string())
+        })?;
+
+        let camera_info = CameraInfo::new(
+            &camera_entry.id().to_string(),
+            "LibCamera Device",
+            "",
+            index as u32,
+        );
+
+        let camera = camera_entry
+            .acquire()
+            .map_err(|why| NokhwaError::OpenDeviceError(index.to_string(), why.to_string()))?;
+
+        let allocator = FrameBufferAllocator::new(&camera);
+
+        // CameraFormat::default()'s format is FrameFormat::MJPEG, which pixel_format_for rejects -
+        // start from a format the libcamera backend can actually negotiate so a freshly
+        // constructed device initializes without the caller having to call set_camera_format first.
+        let mut camera_format = CameraFormat::default();
+        camera_format.set_format(FrameFormat::NV12);
+
+        Ok(LibCameraCaptureDevice {
+            camera_info,
+            camera_format,
+            camera,
+            allocator,
+            planes: Vec::new(),
+            requests: Vec::new(),
+            stream_open: false,
+        })
+    }
+
+    /// Negotiates a stream configuration matching [`camera_format()`](CaptureBackendTrait::camera_format),
+    /// records the per-plane [`PlaneLayout`]s it reports so later frames can be stitched correctly
+    /// regardless of how many planes the sensor's pixel format uses, and attaches each allocated
+    /// buffer to a fresh [`Request`] ready for [`open_stream`](CaptureBackendTrait::open_stream) to
+    /// queue.
+    /// # Errors
+    /// If the camera rejects every configuration it is offered, or buffer allocation fails, this
+    /// will error.
+    fn negotiate_stream(&mut self) -> Result<(), NokhwaError> {
+        let mut config = self
+            .camera
+            .generate_configuration(&[StreamRole::VideoRecording])
+            .ok_or_else(|| NokhwaError::StructureError {
+                structure: "StreamConfiguration".to_string(),
+                error: "libcamera refused to generate a configuration".to_string(),
+            })?;
+
+        {
+            let resolution = self.camera_format.resolution();
+            let mut stream_cfg = config
+                .get_mut(0)
+                .ok_or_else(|| NokhwaError::StructureError {
+                    structure: "StreamConfiguration".to_string(),
+                    error: "no stream configuration to negotiate".to_string(),
+                })?;
+            stream_cfg.set_size(Size {
+                width: resolution.width(),
+                height: resolution.height(),
+            });
+            stream_cfg.set_pixel_format(pixel_format_for(self.camera_format.format())?);
+        }
+
+        if config.validate() == CameraConfigurationStatus::Invalid {
+            return Err(NokhwaError::SetPropertyError {
+                property: "StreamConfiguration".to_string(),
+                value: format!("{:?}", self.camera_format),
+                error: "libcamera could not negotiate a valid stream configuration".to_string(),
+            });
+        }
+
+        self.camera
+            .configure(&mut config)
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "StreamConfiguration".to_string(),
+                value: format!("{:?}", self.camera_format),
+                error: why.to_string(),
+            })?;
+
+        // libcamera may adjust the requested size to the nearest one the sensor actually
+        // supports, so read the negotiated configuration back instead of assuming our request
+        // for `self.camera_format` stuck exactly.
+        let stream_cfg = config.get(0).ok_or_else(|| NokhwaError::StructureError {
+            structure: "StreamConfiguration".to_string(),
+            error: "no stream configuration to read back".to_string(),
+        })?;
+        let negotiated_size = stream_cfg.get_size();
+        self.camera_format.set_resolution(Resolution::new(
+            negotiated_size.width,
+            negotiated_size.height,
+        ));
+
+        let stride = stream_cfg.get_stride() as usize;
+        // libcamera pads each row out to `stride` bytes, but the formats pixel_format_for allows
+        // (NV12, GrayU8) are both 1-byte-per-sample, so their real per-row data is exactly
+        // `width` bytes in every plane - the luma plane and the (byte-for-byte equal width)
+        // interleaved chroma plane alike. This assumption breaks for 2-bytes-per-sample or
+        // compressed formats, which is exactly why pixel_format_for rejects them.
+        let row_bytes = (negotiated_size.width as usize).min(stride.max(1));
+        let stream = stream_cfg
+            .stream()
+            .ok_or_else(|| NokhwaError::StructureError {
+                structure: "StreamConfiguration".to_string(),
+                error: "negotiated stream configuration has no associated stream".to_string(),
+            })?;
+        let buffers = self
+            .allocator
+            .alloc(&stream)
+            .map_err(|why| NokhwaError::OpenStreamError(why.to_string()))?;
+
+        // Record each plane's row layout up front: these stay fixed for the lifetime of the
+        // stream, so `frame_raw` only has to copy bytes, not re-derive the layout per frame.
+        self.planes.clear();
+        if let Some(buffer) = buffers.first() {
+            for plane in buffer.planes() {
+                let length = plane.length() as usize;
+                let rows = if stride > 0 { length / stride } else { 0 };
+                self.planes.push(PlaneLayout {
+                    rows,
+                    row_bytes,
+                    stride,
+                });
+            }
+        }
+
+        // Attach each allocated buffer to its own Request up front, so open_stream can queue them
+        // all and the camera always has buffers in flight for frame_raw's wait_for_request to
+        // complete.
+        self.requests.clear();
+        for buffer in buffers {
+            let mut request = self.camera.create_request(None).ok_or_else(|| {
+                NokhwaError::OpenStreamError("failed to create a libcamera request".to_string())
+            })?;
+            request
+                .add_buffer(&stream, buffer)
+                .map_err(|why| NokhwaError::OpenStreamError(why.to_string()))?;
+            self.requests.push(request);
+        }
+
+        Ok(())
+    }
+
+    /// Stitches the per-plane data of a captured frame buffer into one tightly-packed `Vec<u8>`,
+    /// using [`self.planes`](Self::planes) as the row layout for `buffer.data()`'s plane slices.
+    fn stitch_planes(&self, buffer: &MemoryMappedFrameBuffer<FrameBuffer>) -> Vec<u8> {
+        stitch_plane_data(&buffer.data(), &self.planes)
+    }
+}
+
+/// Stitches a frame's per-plane slices into one tightly-packed `Vec<u8>`, walking each plane row
+/// by row using its own stride and dropping any stride padding past each row's real `row_bytes`,
+/// rather than assuming the whole frame is one contiguous, already-packed allocation. `data[i]`
+/// is expected to already start at plane `i`'s own offset, so rows are indexed relative to the
+/// start of each slice rather than re-adding an offset. Pulled out of
+/// [`LibCameraCaptureDevice::stitch_planes`] so the stride math can be tested without a real
+/// `libcamera` frame buffer.
+fn stitch_plane_data(data: &[impl AsRef<[u8]>], planes: &[PlaneLayout]) -> Vec<u8> {
+    let mut stitched = Vec::with_capacity(planes.iter().map(|p| p.row_bytes * p.rows).sum());
+    for (index, plane) in planes.iter().enumerate() {
+        if let Some(data) = data.get(index) {
+            let data = data.as_ref();
+            for row in 0..plane.rows {
+                let row_start = row * plane.stride;
+                let row_end = (row_start + plane.row_bytes).min(data.len());
+                if row_start < row_end {
+                    stitched.extend_from_slice(&data[row_start..row_end]);
+                }
+            }
+        }
+    }
+    stitched
+}
+
+impl CaptureBackendTrait for LibCameraCaptureDevice {
+    fn init(&mut self) -> Result<CameraFormat, NokhwaError> {
+        self.negotiate_stream()?;
+        Ok(self.camera_format)
+    }
+
+    fn backend(&self) -> ApiBackend {
+        ApiBackend::LibCamera
+    }
+
+    fn camera_info(&self) -> &CameraInfo {
+        &self.camera_info
+    }
+
+    fn refresh_camera_format(&mut self) -> Result<(), NokhwaError> {
+        self.negotiate_stream()
+    }
+
+    fn camera_format(&self) -> CameraFormat {
+        self.camera_format
+    }
+
+    fn set_camera_format(&mut self, new_fmt: CameraFormat) -> Result<(), NokhwaError> {
+        self.camera_format = new_fmt;
+        self.negotiate_stream()
+    }
+
+    fn compatible_list_by_resolution(
+        &mut self,
+        fourcc: FrameFormat,
+    ) -> Result<HashMap<Resolution, Vec<u32>>, NokhwaError> {
+        let config = self
+            .camera
+            .generate_configuration(&[StreamRole::VideoRecording])
+            .ok_or_else(|| NokhwaError::StructureError {
+                structure: "StreamConfiguration".to_string(),
+                error: "libcamera refused to generate a configuration".to_string(),
+            })?;
+        let stream_cfg = config.get(0).ok_or_else(|| NokhwaError::StructureError {
+            structure: "StreamConfiguration".to_string(),
+            error: "no stream configuration to query".to_string(),
+        })?;
+
+        let pixel_format = pixel_format_for(fourcc)?;
+        let mut resolutions = HashMap::new();
+        for size in stream_cfg.formats().sizes(pixel_format) {
+            // libcamera reports the sizes a pixel format supports, not a discrete list of frame
+            // rates per size, so the only rate we can honestly claim here is the one currently
+            // configured on the camera.
+            resolutions.insert(
+                Resolution::new(size.width, size.height),
+                vec![self.camera_format.frame_rate()],
+            );
+        }
+        Ok(resolutions)
+    }
+
+    fn compatible_fourcc(&mut self) -> Result<Vec<FrameFormat>, NokhwaError> {
+        // Limited to the formats pixel_format_for/stitch_planes can actually stitch correctly -
+        // see pixel_format_for's doc comment for why MJPEG, YUYV and GrayU16 are excluded.
+        Ok(vec![FrameFormat::NV12, FrameFormat::GrayU8])
+    }
+
+    fn resolution(&self) -> Resolution {
+        self.camera_format.resolution()
+    }
+
+    fn set_resolution(&mut self, new_res: Resolution) -> Result<(), NokhwaError> {
+        self.camera_format.set_resolution(new_res);
+        self.negotiate_stream()
+    }
+
+    fn frame_rate(&self) -> u32 {
+        self.camera_format.frame_rate()
+    }
+
+    fn set_frame_rate(&mut self, new_fps: u32) -> Result<(), NokhwaError> {
+        self.camera_format.set_frame_rate(new_fps);
+        self.negotiate_stream()
+    }
+
+    fn frame_format(&self) -> FrameFormat {
+        self.camera_format.format()
+    }
+
+    fn set_frame_format(&mut self, fourcc: FrameFormat) -> Result<(), NokhwaError> {
+        self.camera_format.set_format(fourcc);
+        self.negotiate_stream()
+    }
+
+    fn camera_control(&self, control: KnownCameraControl) -> Result<CameraControl, NokhwaError> {
+        // libcamera exposes controls through its own per-camera ControlList rather than
+        // nokhwa's KnownCameraControl enum, and mapping between the two isn't wired up yet; fail
+        // the individual lookup instead of panicking.
+        Err(NokhwaError::GetPropertyError {
+            property: format!("{control:?}"),
+            error: "libcamera backend does not yet expose individual camera controls".to_string(),
+        })
+    }
+
+    fn camera_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        Ok(Vec::new())
+    }
+
+    fn set_camera_control(
+        &mut self,
+        id: KnownCameraControl,
+        value: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
+        Err(NokhwaError::SetPropertyError {
+            property: format!("{id:?}"),
+            value: format!("{value:?}"),
+            error: "libcamera backend does not yet expose individual camera controls".to_string(),
+        })
+    }
+
+    fn open_stream(&mut self) -> Result<(), NokhwaError> {
+        // self.requests is fully drained below every time this runs, so a restart cycle
+        // (stop_stream() then open_stream() again) would otherwise start the camera with no
+        // requests queued - re-negotiate to refill it rather than silently capturing nothing.
+        if self.requests.is_empty() {
+            self.negotiate_stream()?;
+        }
+        self.camera
+            .start(None)
+            .map_err(|why| NokhwaError::OpenStreamError(why.to_string()))?;
+        // negotiate_stream() only attaches buffers to requests; nothing captures until those
+        // requests are actually queued to the camera.
+        for request in self.requests.drain(..) {
+            self.camera
+                .queue_request(request)
+                .map_err(|why| NokhwaError::OpenStreamError(why.to_string()))?;
+        }
+        self.stream_open = true;
+        Ok(())
+    }
+
+    fn is_stream_open(&self) -> bool {
+        self.stream_open
+    }
+
+    fn frame<'b>(&mut self) -> Result<Buffer<'b>, NokhwaError> {
+        let raw = self.frame_raw()?.into_owned();
+        Ok(Buffer::new(
+            self.camera_format.resolution(),
+            Cow::Owned(raw),
+            self.camera_format.format(),
+        ))
+    }
+
+    fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
+        if !self.stream_open {
+            return Err(NokhwaError::ReadFrameError(
+                "stream is not open".to_string(),
+            ));
+        }
+
+        let request = self
+            .camera
+            .wait_for_request(Duration::from_secs(2))
+            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+
+        let framebuffer: &MemoryMappedFrameBuffer<FrameBuffer> = request
+            .buffer()
+            .ok_or_else(|| NokhwaError::ReadFrameError("request had no buffer".to_string()))?;
+
+        // The sensor may hand us the frame as several distinct plane buffers (e.g. Y and
+        // interleaved UV for NV12) rather than one contiguous allocation, so stitch them using
+        // each plane's own stride/offset instead of treating `framebuffer` as flat bytes.
+        let stitched = self.stitch_planes(framebuffer);
+
+        request.reuse(ReuseFlag::REUSE_BUFFERS);
+        self.camera
+            .queue_request(request)
+            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+
+        Ok(Cow::Owned(stitched))
+    }
+
+    fn stop_stream(&mut self) -> Result<(), NokhwaError> {
+        if self.stream_open {
+            self.camera
+                .stop()
+                .map_err(|why| NokhwaError::GeneralError(why.to_string()))?;
+            self.stream_open = false;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LibCameraCaptureDevice {
+    fn drop(&mut self) {
+        let _stop_stream_err = self.stop_stream();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stitch_plane_data_single_plane_no_padding() {
+        let plane = PlaneLayout {
+            rows: 2,
+            row_bytes: 3,
+            stride: 3,
+        };
+        let data: [&[u8]; 1] = [&[1, 2, 3, 4, 5, 6]];
+        assert_eq!(stitch_plane_data(&data, &[plane]), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn stitch_plane_data_drops_stride_padding() {
+        // Each row has 4 real bytes but is padded out to an 6-byte stride.
+        let plane = PlaneLayout {
+            rows: 2,
+            row_bytes: 4,
+            stride: 6,
+        };
+        let data: [&[u8]; 1] = [&[1, 2, 3, 4, 0, 0, 5, 6, 7, 8, 0, 0]];
+        assert_eq!(
+            stitch_plane_data(&data, &[plane]),
+            vec![1, 2, 3, 4, 5, 6, 7, 8]
+        );
+    }
+
+    #[test]
+    fn stitch_plane_data_stitches_multiple_planes_in_order() {
+        // e.g. NV12's Y plane followed by its interleaved UV plane.
+        let y_plane = PlaneLayout {
+            rows: 2,
+            row_bytes: 2,
+            stride: 2,
+        };
+        let uv_plane = PlaneLayout {
+            rows: 1,
+            row_bytes: 2,
+            stride: 2,
+        };
+        let data: [&[u8]; 2] = [&[1, 2, 3, 4], &[9, 9]];
+        assert_eq!(
+            stitch_plane_data(&data, &[y_plane, uv_plane]),
+            vec![1, 2, 3, 4, 9, 9]
+        );
+    }
+
+    #[test]
+    fn stitch_plane_data_truncates_rows_past_a_too_short_plane() {
+        // Plane claims 3 rows of 4 bytes but the underlying slice only holds 1.5 rows.
+        let plane = PlaneLayout {
+            rows: 3,
+            row_bytes: 4,
+            stride: 4,
+        };
+        let data: [&[u8]; 1] = [&[1, 2, 3, 4, 5, 6]];
+        assert_eq!(stitch_plane_data(&data, &[plane]), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn stitch_plane_data_missing_plane_slice_is_skipped() {
+        let plane = PlaneLayout {
+            rows: 1,
+            row_bytes: 2,
+            stride: 2,
+        };
+        let data: [&[u8]; 0] = [];
+        assert_eq!(stitch_plane_data(&data, &[plane]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn stitch_plane_data_zero_rows_is_empty() {
+        let plane = PlaneLayout {
+            rows: 0,
+            row_bytes: 4,
+            stride: 4,
+        };
+        let data: [&[u8]; 1] = [&[1, 2, 3, 4]];
+        assert_eq!(stitch_plane_data(&data, &[plane]), Vec::<u8>::new());
+    }
+}
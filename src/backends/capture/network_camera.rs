@@ -19,10 +19,18 @@ use crate::{
     CameraInfo, CaptureBackendTrait, ControlValueSetter, FrameFormat, KnownCameraControl,
     NokhwaError, Resolution,
 };
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
 use image::{buffer::ConvertBuffer, ImageBuffer, Rgb, RgbaImage};
 use std::borrow::Cow;
-use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
 #[cfg(feature = "output-wgpu")]
 use wgpu::{
     Device as WgpuDevice, Extent3d, ImageCopyTexture, ImageDataLayout, Queue as WgpuQueue,
@@ -30,11 +38,341 @@ use wgpu::{
     TextureUsages,
 };
 
+/// The number of frames kept in flight between the decode thread and the consumer before the
+/// oldest one is dropped in favour of the newest.
+const STREAM_POOL_DEPTH: usize = 3;
+
+/// How long the decode thread waits before retrying after a failed `OpenCvCaptureDevice::frame_raw()`
+/// call or a frame it cannot downscale, so a persistently erroring backend backs off instead of
+/// busy-looping.
+const FRAME_ERROR_BACKOFF: Duration = Duration::from_millis(50);
+
+/// A single recycled frame produced by [`NetworkCamera`]'s background streaming pipeline.
+///
+/// Dropping this value returns its backing allocation to the free pool so the decode thread can
+/// reuse it for the next frame instead of allocating a new `Vec<u8>`. Reflects whatever
+/// [`frame_format()`](NetworkCamera::frame_format) and [`downscale()`](NetworkCamera::downscale)
+/// were set to when [`open_stream()`](NetworkCamera::open_stream) was (last) called, the same as
+/// [`frame()`](NetworkCamera::frame) and [`frame_to_buffer()`](NetworkCamera::frame_to_buffer).
+pub struct PooledFrame {
+    resolution: Resolution,
+    frame_format: FrameFormat,
+    data: Vec<u8>,
+    free_tx: Sender<Vec<u8>>,
+}
+
+impl PooledFrame {
+    /// The resolution of this frame.
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// The pixel format requested from the backend when the stream was opened. For
+    /// [`FrameFormat::GrayU8`] and [`FrameFormat::GrayU16`] this is also the layout of
+    /// [`data()`](PooledFrame::data); for every other format `data()` holds decoded RGB24 pixels.
+    pub fn frame_format(&self) -> FrameFormat {
+        self.frame_format
+    }
+
+    /// The raw decoded bytes of this frame: single-channel samples for [`FrameFormat::GrayU8`]
+    /// and [`FrameFormat::GrayU16`], RGB24 pixels for every other
+    /// [`frame_format()`](PooledFrame::frame_format).
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Drop for PooledFrame {
+    fn drop(&mut self) {
+        // The free pool is bounded to STREAM_POOL_DEPTH allocations; a blocking send here could
+        // stall whichever thread drops a frame if more than that many are ever outstanding at
+        // once, so just let the allocation go if the pool is already full.
+        let _ = self.free_tx.try_send(std::mem::take(&mut self.data));
+    }
+}
+
+/// Handle to the background decode thread feeding a [`NetworkCamera`]'s frame pool.
+struct StreamPipeline {
+    filled_rx: Receiver<PooledFrame>,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl StreamPipeline {
+    /// Spawns the decode thread. `frame_format`/`downscale` are a snapshot of
+    /// [`NetworkCamera::frame_format()`]/[`NetworkCamera::downscale()`] at the moment the stream
+    /// was opened - changing either while already streaming only takes effect on the next
+    /// [`NetworkCamera::open_stream()`] call, which tears down and respawns this pipeline
+    /// ([`NetworkCamera::set_ip()`] makes that call itself when a stream is running).
+    fn spawn(
+        backend: Arc<Mutex<OpenCvCaptureDevice>>,
+        buffer_size: usize,
+        frame_format: FrameFormat,
+        downscale: u32,
+        decoded_frame_tap: Arc<Mutex<Option<Box<dyn Write + Send>>>>,
+    ) -> Self {
+        let (filled_tx, filled_rx) = bounded::<PooledFrame>(STREAM_POOL_DEPTH);
+        let (free_tx, free_rx) = bounded::<Vec<u8>>(STREAM_POOL_DEPTH);
+        for _ in 0..STREAM_POOL_DEPTH {
+            let _ = free_tx.send(vec![0_u8; buffer_size]);
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread_filled_rx = filled_rx.clone();
+
+        let worker = thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                let mut guard = backend.lock().unwrap();
+                let resolution = guard.resolution();
+                let raw = match guard.frame_raw() {
+                    Ok(raw) => raw.into_owned(),
+                    // A busy-retry here would pin a core at 100% for as long as the backend keeps
+                    // erroring (e.g. the underlying stream dropped), so back off between attempts
+                    // instead of spinning.
+                    Err(_) => {
+                        drop(guard);
+                        thread::sleep(FRAME_ERROR_BACKOFF);
+                        continue;
+                    }
+                };
+                drop(guard);
+
+                if let Some(tap) = decoded_frame_tap.lock().unwrap().as_mut() {
+                    let _ = tap.write_all(&raw);
+                }
+
+                let (data, out_resolution) = match frame_format {
+                    FrameFormat::GrayU8 | FrameFormat::GrayU16 => {
+                        let bytes_per_sample = if frame_format == FrameFormat::GrayU16 { 2 } else { 1 };
+                        if downscale > 1 {
+                            let scaled = match downscale_mono_checked(
+                                &raw,
+                                resolution.width(),
+                                resolution.height(),
+                                downscale,
+                                bytes_per_sample,
+                            ) {
+                                Ok(scaled) => scaled,
+                                Err(_) => {
+                                    thread::sleep(FRAME_ERROR_BACKOFF);
+                                    continue;
+                                }
+                            };
+                            let out_resolution = Resolution::new(
+                                downscaled_dim(resolution.width(), downscale),
+                                downscaled_dim(resolution.height(), downscale),
+                            );
+                            (scaled, out_resolution)
+                        } else {
+                            (raw, resolution)
+                        }
+                    }
+                    _ => {
+                        if downscale > 1 {
+                            match ImageBuffer::<Rgb<u8>, Vec<u8>>::from_raw(
+                                resolution.width(),
+                                resolution.height(),
+                                raw,
+                            ) {
+                                Some(frame) => {
+                                    let scaled = downscale_rgb(&frame, downscale);
+                                    let out_resolution =
+                                        Resolution::new(scaled.width(), scaled.height());
+                                    (scaled.into_raw(), out_resolution)
+                                }
+                                None => {
+                                    thread::sleep(FRAME_ERROR_BACKOFF);
+                                    continue;
+                                }
+                            }
+                        } else {
+                            (raw, resolution)
+                        }
+                    }
+                };
+
+                // Reuse a recycled buffer if one is free; only when the pool is momentarily
+                // empty (e.g. the consumer is holding every PooledFrame at once) do we fall back
+                // to a fresh, correctly-sized allocation instead of the default-then-resize that
+                // would reallocate from zero either way.
+                let mut backing = free_rx
+                    .try_recv()
+                    .unwrap_or_else(|_| Vec::with_capacity(data.len()));
+                if backing.len() != data.len() {
+                    backing.resize(data.len(), 0);
+                }
+                backing.copy_from_slice(&data);
+
+                let pooled = PooledFrame {
+                    resolution: out_resolution,
+                    frame_format,
+                    data: backing,
+                    free_tx: free_tx.clone(),
+                };
+
+                // Bounded queue, newest-frame-wins: if the consumer is behind, evict the
+                // oldest frame rather than stall the decode thread.
+                if let Err(TrySendError::Full(pooled)) = filled_tx.try_send(pooled) {
+                    let _ = thread_filled_rx.try_recv();
+                    let _ = filled_tx.try_send(pooled);
+                }
+            }
+        });
+
+        StreamPipeline {
+            filled_rx,
+            stop,
+            worker: Some(worker),
+        }
+    }
+}
+
+impl Drop for StreamPipeline {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Expands a single-channel monochrome frame into an interleaved RGBA buffer for display.
+/// [`FrameFormat::GrayU16`] samples are truncated to their high byte, matching the common
+/// 8-bit-per-channel display path.
+fn mono_to_rgba(raw: &[u8], format: FrameFormat) -> Vec<u8> {
+    match format {
+        FrameFormat::GrayU16 => raw
+            .chunks_exact(2)
+            .flat_map(|sample| {
+                let intensity = sample[1];
+                [intensity, intensity, intensity, 255]
+            })
+            .collect(),
+        _ => raw
+            .iter()
+            .flat_map(|&intensity| [intensity, intensity, intensity, 255])
+            .collect(),
+    }
+}
+
+/// The size of a dimension after being decimated by an integer `factor`, clamped to at least 1.
+fn downscaled_dim(dim: u32, factor: u32) -> u32 {
+    (dim / factor.max(1)).max(1)
+}
+
+/// Downscales a RGB24 frame by averaging each `factor x factor` block of pixels. A 0-width or
+/// 0-height `frame` is returned unchanged, since `downscaled_dim` would otherwise clamp the
+/// output to 1x1 and the averaging loop below would divide by a `count` of 0.
+fn downscale_rgb(frame: &ImageBuffer<Rgb<u8>, Vec<u8>>, factor: u32) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    if frame.width() == 0 || frame.height() == 0 {
+        return frame.clone();
+    }
+    let out_width = downscaled_dim(frame.width(), factor);
+    let out_height = downscaled_dim(frame.height(), factor);
+    let mut out = ImageBuffer::new(out_width, out_height);
+    for out_y in 0..out_height {
+        for out_x in 0..out_width {
+            let mut sum = [0_u32; 3];
+            let mut count = 0_u32;
+            for dy in 0..factor {
+                let src_y = out_y * factor + dy;
+                if src_y >= frame.height() {
+                    continue;
+                }
+                for dx in 0..factor {
+                    let src_x = out_x * factor + dx;
+                    if src_x >= frame.width() {
+                        continue;
+                    }
+                    let pixel = frame.get_pixel(src_x, src_y);
+                    sum[0] += pixel[0] as u32;
+                    sum[1] += pixel[1] as u32;
+                    sum[2] += pixel[2] as u32;
+                    count += 1;
+                }
+            }
+            out.put_pixel(
+                out_x,
+                out_y,
+                Rgb([
+                    (sum[0] / count) as u8,
+                    (sum[1] / count) as u8,
+                    (sum[2] / count) as u8,
+                ]),
+            );
+        }
+    }
+    out
+}
+
+/// Downscales a raw mono plane by subsampling the top-left sample of each `factor x factor`
+/// block. Subsampling (rather than averaging) sidesteps having to interpret sample byte order for
+/// [`FrameFormat::GrayU16`].
+///
+/// `raw` is expected to be exactly `width * height * bytes_per_sample` bytes of tightly-packed,
+/// single-channel samples; if it is shorter (e.g. the backend handed back a decoded multi-channel
+/// buffer instead of a mono one) or either dimension is 0, an empty buffer is returned rather than
+/// reading out of bounds.
+fn downscale_mono(raw: &[u8], width: u32, height: u32, factor: u32, bytes_per_sample: u32) -> Vec<u8> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let sample_bytes = bytes_per_sample as usize;
+    let stride = width as usize * sample_bytes;
+    if raw.len() < stride * height as usize {
+        return Vec::new();
+    }
+    let out_width = downscaled_dim(width, factor);
+    let out_height = downscaled_dim(height, factor);
+    let mut out = Vec::with_capacity((out_width * out_height) as usize * sample_bytes);
+    for out_y in 0..out_height {
+        let src_y = (out_y * factor).min(height - 1) as usize;
+        for out_x in 0..out_width {
+            let src_x = (out_x * factor).min(width - 1) as usize;
+            let offset = src_y * stride + src_x * sample_bytes;
+            out.extend_from_slice(&raw[offset..offset + sample_bytes]);
+        }
+    }
+    out
+}
+
+/// Calls [`downscale_mono`], turning its "too short to be a valid mono frame" signal (an empty
+/// `Vec`) into a proper error instead of leaving callers to notice on their own that the output
+/// doesn't match the size they expected.
+/// # Errors
+/// If `raw` is shorter than `width * height * bytes_per_sample` bytes.
+fn downscale_mono_checked(
+    raw: &[u8],
+    width: u32,
+    height: u32,
+    factor: u32,
+    bytes_per_sample: u32,
+) -> Result<Vec<u8>, NokhwaError> {
+    let stride = width as usize * bytes_per_sample as usize;
+    if width != 0 && height != 0 && raw.len() < stride * height as usize {
+        return Err(NokhwaError::ReadFrameError(format!(
+            "mono frame buffer is {} bytes, need at least {}",
+            raw.len(),
+            stride * height as usize
+        )));
+    }
+    Ok(downscale_mono(raw, width, height, factor, bytes_per_sample))
+}
+
 /// A struct that supports IP Cameras via the `OpenCV` backend.
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-ipcam")))]
 pub struct NetworkCamera {
     ip: String,
-    opencv_backend: RefCell<OpenCvCaptureDevice>,
+    opencv_backend: Arc<Mutex<OpenCvCaptureDevice>>,
+    pipeline: Option<StreamPipeline>,
+    frame_format: FrameFormat,
+    downscale: u32,
+    /// Shared with the background [`StreamPipeline`] worker (if one is running) so that
+    /// [`tap_decoded_frame()`](NetworkCamera::tap_decoded_frame) and
+    /// [`stop_decoded_frame_tap()`](NetworkCamera::stop_decoded_frame_tap) take effect for
+    /// streaming consumers too, not just [`frame()`](NetworkCamera::frame).
+    decoded_frame_tap: Arc<Mutex<Option<Box<dyn Write + Send>>>>,
 }
 
 impl NetworkCamera {
@@ -45,7 +383,11 @@ impl NetworkCamera {
         let opencv_camera = OpenCvCaptureDevice::new_ip_camera(ip.clone())?;
         Ok(NetworkCamera {
             ip,
-            opencv_backend: RefCell::new(opencv_camera),
+            opencv_backend: Arc::new(Mutex::new(opencv_camera)),
+            pipeline: None,
+            frame_format: FrameFormat::MJPEG,
+            downscale: 1,
+            decoded_frame_tap: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -54,54 +396,274 @@ impl NetworkCamera {
         self.ip.clone()
     }
 
+    /// Gets the pixel format requested from the backend.
+    pub fn frame_format(&self) -> FrameFormat {
+        self.frame_format
+    }
+
+    /// Sets the pixel format requested from the backend, propagating the request to the
+    /// underlying `OpenCV` device so it actually decodes (or passes through) a mono stream.
+    /// [`FrameFormat::GrayU8`] and [`FrameFormat::GrayU16`] then read the raw single-channel
+    /// stream straight from the backend instead of assuming RGB24, which is needed for
+    /// mono/scientific sensors.
+    /// # Errors
+    /// If the backend rejects the requested format, this will error.
+    pub fn set_frame_format(&mut self, format: FrameFormat) -> Result<(), NokhwaError> {
+        self.opencv_backend
+            .lock()
+            .unwrap()
+            .set_frame_format(format)?;
+        self.frame_format = format;
+        Ok(())
+    }
+
+    /// Gets the current integer downscale factor.
+    pub fn downscale(&self) -> u32 {
+        self.downscale
+    }
+
+    /// Sets the integer decimation factor applied during [`frame()`](NetworkCamera::frame) and
+    /// [`frame_to_buffer()`](NetworkCamera::frame_to_buffer). A `factor` of 1 (the default) is a
+    /// no-op; a `factor` of N averages each `N x N` block of an RGB frame, or, for
+    /// [`FrameFormat::GrayU8`] and [`FrameFormat::GrayU16`], keeps the top-left sample of each
+    /// block, so e.g. a 4K stream can be consumed at quarter resolution without a separate resize
+    /// pass.
+    /// # Errors
+    /// If `factor` is 0, this will error.
+    pub fn set_downscale(&mut self, factor: u32) -> Result<(), NokhwaError> {
+        if factor == 0 {
+            return Err(NokhwaError::ReadFrameError(
+                "Downscale factor must be at least 1".to_string(),
+            ));
+        }
+        self.downscale = factor;
+        Ok(())
+    }
+
+    /// Taps the backend's decoded per-frame byte buffer to a user-provided [`Write`]r, in
+    /// parallel with normal [`frame()`](NetworkCamera::frame) delivery and the
+    /// [`poll_frame()`](NetworkCamera::poll_frame)/[`try_recv_frame()`](NetworkCamera::try_recv_frame)
+    /// streaming path alike.
+    ///
+    /// This is **not** an archival copy of the on-the-wire MJPEG/H264 payload: `OpenCvCaptureDevice`
+    /// only ever exposes already-decoded pixel bytes through `frame_raw()`, with no supported way
+    /// to intercept the compressed bytes before OpenCV decodes them short of opening a second,
+    /// independent connection to the camera - which would mean pulling every frame over the
+    /// network twice, and `OpenCvCaptureDevice` only stores the URL/credentials OpenCV itself was
+    /// opened with, not a protocol this code could reliably replicate. Given that tradeoff, this
+    /// taps the decoded bytes instead, which is still useful for archival or for comparing against
+    /// [`frame()`](NetworkCamera::frame)'s output when debugging a downscale or format bug, but it
+    /// cannot be replayed through a decoder to recover the original stream. Replaces any
+    /// previously set tap.
+    pub fn tap_decoded_frame(&mut self, writer: Box<dyn Write + Send>) {
+        *self.decoded_frame_tap.lock().unwrap() = Some(writer);
+    }
+
+    /// Convenience wrapper around [`tap_decoded_frame()`](NetworkCamera::tap_decoded_frame) that opens (or
+    /// creates) `path` and taps the decoded per-frame byte buffer to it.
+    /// # Errors
+    /// If `path` cannot be created, this will error.
+    pub fn decoded_frame_to_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), NokhwaError> {
+        let file = File::create(path).map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+        self.tap_decoded_frame(Box::new(file));
+        Ok(())
+    }
+
+    /// Stops tapping the decoded frame buffer.
+    pub fn stop_decoded_frame_tap(&mut self) {
+        *self.decoded_frame_tap.lock().unwrap() = None;
+    }
+
+    /// If a frame tap is set, writes `raw` (the exact bytes decoded into the frame that was just
+    /// captured) to it.
+    /// # Errors
+    /// If the tap's writer returns an error, this will error.
+    fn tap_current_decoded_frame(&self, raw: &[u8]) -> Result<(), NokhwaError> {
+        if let Some(tap) = self.decoded_frame_tap.lock().unwrap().as_mut() {
+            tap.write_all(raw)
+                .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+        }
+        Ok(())
+    }
+
     /// Sets the IP. Will restart stream if already started.
     /// # Errors
     /// If the IP is invalid or `OpenCV` fails to open the IP, this will error
     pub fn set_ip(&mut self, ip: String) -> Result<(), NokhwaError> {
-        *self.opencv_backend.borrow_mut() = OpenCvCaptureDevice::new_ip_camera(ip.clone())?;
+        let was_streaming = self.pipeline.is_some();
+        self.pipeline = None;
+        *self.opencv_backend.lock().unwrap() = OpenCvCaptureDevice::new_ip_camera(ip.clone())?;
         self.ip = ip;
+        if was_streaming {
+            self.open_stream()?;
+        }
         Ok(())
     }
 
-    /// Opens stream.
+    /// Opens the stream, spawning the background decode thread that feeds
+    /// [`poll_frame()`](NetworkCamera::poll_frame) and [`try_recv_frame()`](NetworkCamera::try_recv_frame).
+    ///
+    /// The decode thread honors whatever [`frame_format()`](NetworkCamera::frame_format) and
+    /// [`downscale()`](NetworkCamera::downscale) are set to at the moment this is called, the same
+    /// as [`frame()`](NetworkCamera::frame) and [`frame_to_buffer()`](NetworkCamera::frame_to_buffer);
+    /// changing either while already streaming only takes effect the next time `open_stream()` is
+    /// called, since it tears down and respawns the pipeline. The pool is pre-sized to match that
+    /// output, so in steady state the pooled frame buffers are reused rather than reallocated. The
+    /// decode thread still takes each frame from the backend as an owned buffer, and downscaling
+    /// builds a new buffer per frame, before the result is copied into a pooled buffer.
     /// # Errors
     /// If the backend fails to capture the stream this will error
-    fn open_stream(&self) -> Result<(), NokhwaError> {
-        self.opencv_backend.borrow_mut().open_stream()
+    pub fn open_stream(&mut self) -> Result<(), NokhwaError> {
+        self.opencv_backend.lock().unwrap().open_stream()?;
+        let buffer_size = self.min_buffer_size(false);
+        self.pipeline = Some(StreamPipeline::spawn(
+            self.opencv_backend.clone(),
+            buffer_size,
+            self.frame_format,
+            self.downscale,
+            self.decoded_frame_tap.clone(),
+        ));
+        Ok(())
+    }
+
+    /// Gets the latest frame recycled from the streaming pool, waiting for the decode thread to
+    /// produce one if none is ready yet.
+    /// # Errors
+    /// If [`open_stream()`](NetworkCamera::open_stream()) has not been called yet, or the
+    /// streaming pipeline has shut down, this will error.
+    pub fn poll_frame(&self) -> Result<PooledFrame, NokhwaError> {
+        match &self.pipeline {
+            Some(pipeline) => {
+                let mut frame = pipeline
+                    .filled_rx
+                    .recv()
+                    .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+                // Skip past any older frames still queued so the newest one is returned; the
+                // skipped frames go back to the free pool as they drop.
+                while let Ok(newer) = pipeline.filled_rx.try_recv() {
+                    frame = newer;
+                }
+                Ok(frame)
+            }
+            None => Err(NokhwaError::ReadFrameError(
+                "stream is not open".to_string(),
+            )),
+        }
+    }
+
+    /// Gets the latest frame recycled from the streaming pool if one is immediately available,
+    /// without blocking.
+    /// # Errors
+    /// If [`open_stream()`](NetworkCamera::open_stream()) has not been called yet, the streaming
+    /// pipeline has shut down, or no frame is ready yet, this will error.
+    pub fn try_recv_frame(&self) -> Result<PooledFrame, NokhwaError> {
+        match &self.pipeline {
+            Some(pipeline) => {
+                let mut frame = pipeline
+                    .filled_rx
+                    .try_recv()
+                    .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+                // Skip past any older frames still queued so the newest one is returned.
+                while let Ok(newer) = pipeline.filled_rx.try_recv() {
+                    frame = newer;
+                }
+                Ok(frame)
+            }
+            None => Err(NokhwaError::ReadFrameError(
+                "stream is not open".to_string(),
+            )),
+        }
     }
 
-    /// Gets the frame decoded as a RGB24 frame
+    /// Gets the frame decoded as a RGB24 frame, downscaled by [`downscale()`](NetworkCamera::downscale) if set.
     /// # Errors
     /// If the backend fails to capture the stream, or if the decoding fails this will error
     fn frame(&self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
-        self.opencv_backend.borrow_mut().frame()
+        // Pull the raw decoded bytes once and build the frame from them, rather than calling
+        // frame() and frame_raw() separately - each triggers its own VideoCapture read, which
+        // would tap a different, later frame than the one actually decoded here.
+        let mut backend = self.opencv_backend.lock().unwrap();
+        let resolution = backend.resolution();
+        let raw = backend.frame_raw()?.into_owned();
+        drop(backend);
+
+        self.tap_current_decoded_frame(&raw)?;
+
+        let frame = ImageBuffer::<Rgb<u8>, Vec<u8>>::from_raw(resolution.width(), resolution.height(), raw)
+            .ok_or_else(|| NokhwaError::ReadFrameError("decoded frame size mismatch".to_string()))?;
+        if self.downscale <= 1 {
+            return Ok(frame);
+        }
+        Ok(downscale_rgb(&frame, self.downscale))
     }
 
-    /// The minimum buffer size needed to write the current frame (RGB24). If `rgba` is true, it will instead return the minimum size of the RGBA buffer needed.
+    /// The minimum buffer size needed to write the current frame, accounting for the current
+    /// [`downscale()`](NetworkCamera::downscale) factor. For [`FrameFormat::GrayU8`] and
+    /// [`FrameFormat::GrayU16`] this is 1 or 2 bytes per pixel respectively, unless `rgba` is
+    /// true, in which case [`frame_to_buffer()`](NetworkCamera::frame_to_buffer) expands them
+    /// through [`mono_to_rgba`] and this must match that 4-bytes-per-pixel size instead.
+    /// Otherwise this is the RGB24 size, or the RGBA size if `rgba` is true.
     fn min_buffer_size(&self, rgba: bool) -> usize {
-        let resolution = self.opencv_backend.borrow().resolution();
-        if rgba {
-            return (resolution.width() * resolution.height() * 4) as usize;
+        let resolution = self.opencv_backend.lock().unwrap().resolution();
+        let width = downscaled_dim(resolution.width(), self.downscale);
+        let height = downscaled_dim(resolution.height(), self.downscale);
+        let pixels = (width * height) as usize;
+        match self.frame_format {
+            _ if rgba => pixels * 4,
+            FrameFormat::GrayU8 => pixels,
+            FrameFormat::GrayU16 => pixels * 2,
+            _ => pixels * 3,
         }
-        (resolution.width() * resolution.height() * 3) as usize
     }
-    /// Directly writes the current frame(RGB24) into said `buffer`. If `convert_rgba` is true, the buffer written will be written as an RGBA frame instead of a RGB frame. Returns the amount of bytes written on successful capture.
+    /// Directly writes the current frame into said `buffer`, downscaled by
+    /// [`downscale()`](NetworkCamera::downscale) if set. For [`FrameFormat::GrayU8`] and
+    /// [`FrameFormat::GrayU16`] the raw mono samples are written as-is, unless `convert_rgba` is
+    /// true, in which case they are expanded into an RGBA frame for display. For all other
+    /// formats, `convert_rgba` selects between RGB24 and RGBA. Returns the amount of bytes
+    /// written on successful capture.
     /// # Errors
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), or [`open_stream()`](CaptureBackendTrait::open_stream()) has not been called yet, this will error.
     fn frame_to_buffer(&self, buffer: &mut [u8], convert_rgba: bool) -> Result<usize, NokhwaError> {
-        let frame = self.frame()?;
-        let mut frame_data = frame.to_vec();
-        if convert_rgba {
-            let rgba_image: RgbaImage = frame.convert();
-            frame_data = rgba_image.to_vec();
-        }
+        let frame_data = match self.frame_format {
+            FrameFormat::GrayU8 | FrameFormat::GrayU16 => {
+                let resolution = self.opencv_backend.lock().unwrap().resolution();
+                let mut raw = self.opencv_backend.lock().unwrap().frame_raw()?.into_owned();
+                self.tap_current_decoded_frame(&raw)?;
+                if self.downscale > 1 {
+                    let bytes_per_sample = if self.frame_format == FrameFormat::GrayU16 { 2 } else { 1 };
+                    raw = downscale_mono_checked(
+                        &raw,
+                        resolution.width(),
+                        resolution.height(),
+                        self.downscale,
+                        bytes_per_sample,
+                    )?;
+                }
+                if convert_rgba {
+                    mono_to_rgba(&raw, self.frame_format)
+                } else {
+                    raw
+                }
+            }
+            _ => {
+                let frame = self.frame()?;
+                if convert_rgba {
+                    let rgba_image: RgbaImage = frame.convert();
+                    rgba_image.to_vec()
+                } else {
+                    frame.to_vec()
+                }
+            }
+        };
         let bytes = frame_data.len();
         buffer.copy_from_slice(&frame_data);
         Ok(bytes)
     }
 
     #[cfg(feature = "output-wgpu")]
-    /// Directly copies a frame to a Wgpu texture. This will automatically convert the frame into a RGBA frame.
+    /// Directly copies a frame to a Wgpu texture. [`FrameFormat::GrayU8`] and
+    /// [`FrameFormat::GrayU16`] are copied as single-channel textures; all other formats are
+    /// automatically converted into a RGBA frame.
     /// # Errors
     /// If the frame cannot be captured or the resolution is 0 on any axis, this will error.
     fn frame_texture<'a>(
@@ -110,6 +672,10 @@ impl NetworkCamera {
         queue: &WgpuQueue,
         label: Option<&'a str>,
     ) -> Result<WgpuTexture, NokhwaError> {
+        if matches!(self.frame_format, FrameFormat::GrayU8 | FrameFormat::GrayU16) {
+            return self.mono_frame_texture(device, queue, label);
+        }
+
         use std::num::NonZeroU32;
         let frame = self.frame()?;
         let rgba_frame: RgbaImage = frame.convert();
@@ -159,11 +725,101 @@ impl NetworkCamera {
         Ok(texture)
     }
 
+    #[cfg(feature = "output-wgpu")]
+    /// Copies a raw mono frame straight into a single-channel Wgpu texture, mapping
+    /// [`FrameFormat::GrayU8`] to [`TextureFormat::R8Unorm`] and [`FrameFormat::GrayU16`] to
+    /// [`TextureFormat::R16Uint`], downscaled by [`downscale()`](NetworkCamera::downscale) if set -
+    /// matching [`frame_texture()`](NetworkCamera::frame_texture)'s RGB path, which downscales via
+    /// [`frame()`](NetworkCamera::frame).
+    /// # Errors
+    /// If the frame cannot be captured or the resolution is 0 on any axis, this will error.
+    fn mono_frame_texture<'a>(
+        &mut self,
+        device: &WgpuDevice,
+        queue: &WgpuQueue,
+        label: Option<&'a str>,
+    ) -> Result<WgpuTexture, NokhwaError> {
+        use std::num::NonZeroU32;
+        let raw = self.opencv_backend.lock().unwrap().frame_raw()?.into_owned();
+        let resolution = self.opencv_backend.lock().unwrap().resolution();
+        // downscaled_dim clamps a 0 dimension up to 1, so reject it here rather than creating a
+        // 1x1 texture for an empty frame.
+        if resolution.width() == 0 || resolution.height() == 0 {
+            return Err(NokhwaError::ReadFrameError(
+                "frame resolution is 0 on an axis".to_string(),
+            ));
+        }
+
+        let (format, bytes_per_pixel) = match self.frame_format {
+            FrameFormat::GrayU16 => (TextureFormat::R16Uint, 2),
+            _ => (TextureFormat::R8Unorm, 1),
+        };
+
+        let raw = if self.downscale > 1 {
+            downscale_mono_checked(
+                &raw,
+                resolution.width(),
+                resolution.height(),
+                self.downscale,
+                bytes_per_pixel,
+            )?
+        } else {
+            raw
+        };
+        let out_width = downscaled_dim(resolution.width(), self.downscale);
+        let out_height = downscaled_dim(resolution.height(), self.downscale);
+
+        let texture_size = Extent3d {
+            width: out_width,
+            height: out_height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = device.create_texture(&TextureDescriptor {
+            label,
+            size: texture_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+        });
+
+        let width_nonzero = match NonZeroU32::try_from(bytes_per_pixel * out_width) {
+            Ok(w) => Some(w),
+            Err(why) => return Err(NokhwaError::ReadFrameError(why.to_string())),
+        };
+
+        let height_nonzero = match NonZeroU32::try_from(out_height) {
+            Ok(h) => Some(h),
+            Err(why) => return Err(NokhwaError::ReadFrameError(why.to_string())),
+        };
+
+        queue.write_texture(
+            ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            &raw,
+            ImageDataLayout {
+                offset: 0,
+                bytes_per_row: width_nonzero,
+                rows_per_image: height_nonzero,
+            },
+            texture_size,
+        );
+
+        Ok(texture)
+    }
+
     /// Will drop the stream.
     /// # Errors
     /// Please check the `Quirks` section of each backend.
     fn stop_stream(&mut self) -> Result<(), NokhwaError> {
-        self.opencv_backend.borrow_mut().stop_stream()
+        self.pipeline = None;
+        self.opencv_backend.lock().unwrap().stop_stream()
     }
 }
 
@@ -268,4 +924,89 @@ impl Drop for NetworkCamera {
     fn drop(&mut self) {
         let _stop_stream_err = self.stop_stream();
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downscaled_dim_divides_and_clamps_to_one() {
+        assert_eq!(downscaled_dim(1920, 2), 960);
+        assert_eq!(downscaled_dim(1921, 2), 960);
+        assert_eq!(downscaled_dim(1, 4), 1);
+        assert_eq!(downscaled_dim(0, 4), 1);
+    }
+
+    #[test]
+    fn downscaled_dim_factor_of_one_is_a_no_op() {
+        assert_eq!(downscaled_dim(1920, 1), 1920);
+    }
+
+    #[test]
+    fn downscale_rgb_zero_dimension_returns_frame_unchanged() {
+        let frame = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(0, 0);
+        let out = downscale_rgb(&frame, 2);
+        assert_eq!((out.width(), out.height()), (0, 0));
+    }
+
+    #[test]
+    fn downscale_rgb_averages_each_block() {
+        // 2x2 frame, factor 2 -> 1x1 output averaging all four pixels.
+        let mut frame = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(2, 2);
+        frame.put_pixel(0, 0, Rgb([0, 0, 0]));
+        frame.put_pixel(1, 0, Rgb([10, 10, 10]));
+        frame.put_pixel(0, 1, Rgb([20, 20, 20]));
+        frame.put_pixel(1, 1, Rgb([30, 30, 30]));
+        let out = downscale_rgb(&frame, 2);
+        assert_eq!((out.width(), out.height()), (1, 1));
+        assert_eq!(*out.get_pixel(0, 0), Rgb([15, 15, 15]));
+    }
+
+    #[test]
+    fn downscale_rgb_non_factor_aligned_dimensions_drop_the_remainder() {
+        // 3x3 at factor 2 -> 1x1, matching downscaled_dim's floor-division + clamp-to-1.
+        let frame = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(3, 3);
+        let out = downscale_rgb(&frame, 2);
+        assert_eq!((out.width(), out.height()), (1, 1));
+    }
+
+    #[test]
+    fn downscale_mono_zero_dimension_returns_empty() {
+        assert_eq!(downscale_mono(&[1, 2, 3, 4], 0, 2, 2, 1), Vec::<u8>::new());
+        assert_eq!(downscale_mono(&[1, 2, 3, 4], 2, 0, 2, 1), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn downscale_mono_short_buffer_returns_empty() {
+        // Claims to be a 4x4 GrayU8 frame but is only 4 bytes long.
+        assert_eq!(downscale_mono(&[1, 2, 3, 4], 4, 4, 2, 1), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn downscale_mono_subsamples_top_left_of_each_block() {
+        // 4x4 GrayU8 plane, factor 2 -> 2x2 output taking the top-left sample of each block.
+        let raw: Vec<u8> = (0..16).collect();
+        let out = downscale_mono(&raw, 4, 4, 2, 1);
+        assert_eq!(out, vec![0, 2, 8, 10]);
+    }
+
+    #[test]
+    fn downscale_mono_checked_errors_on_short_buffer() {
+        let err = downscale_mono_checked(&[1, 2, 3], 4, 4, 2, 1);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn downscale_mono_checked_zero_dimension_ok_and_empty() {
+        let out = downscale_mono_checked(&[1, 2, 3, 4], 0, 4, 2, 1).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn downscale_mono_checked_matches_downscale_mono_on_valid_input() {
+        let raw: Vec<u8> = (0..16).collect();
+        let out = downscale_mono_checked(&raw, 4, 4, 2, 1).unwrap();
+        assert_eq!(out, downscale_mono(&raw, 4, 4, 2, 1));
+    }
 }
\ No newline at end of file
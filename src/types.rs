@@ -0,0 +1,81 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt::{Display, Formatter};
+
+/// A pixel format a capture backend can be asked to produce.
+///
+/// Most backends default to a compressed or interleaved colour format (e.g. [`FrameFormat::MJPEG`]),
+/// but some devices - scientific/industrial sensors and IP cameras in particular - are best
+/// consumed as a raw single-channel stream instead, which [`FrameFormat::GrayU8`] and
+/// [`FrameFormat::GrayU16`] represent.
+///
+/// `#[non_exhaustive]`: this tree only carries the backends under `src/backends/capture`, not
+/// nokhwa-core where the canonical `FrameFormat` lives alongside every other backend's variants,
+/// so this listing must not be treated as complete - matching against it exhaustively would
+/// silently drop whatever variants the canonical enum has that this partial view doesn't.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FrameFormat {
+    /// Motion JPEG.
+    MJPEG,
+    /// YUYV 4:2:2.
+    YUYV,
+    /// NV12 (4:2:0, Y plane followed by an interleaved UV plane).
+    NV12,
+    /// Single-channel, 8 bits per pixel monochrome.
+    GrayU8,
+    /// Single-channel, 16 bits per pixel (little-endian) monochrome.
+    GrayU16,
+}
+
+impl Display for FrameFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameFormat::MJPEG => write!(f, "MJPEG"),
+            FrameFormat::YUYV => write!(f, "YUYV"),
+            FrameFormat::NV12 => write!(f, "NV12"),
+            FrameFormat::GrayU8 => write!(f, "GrayU8"),
+            FrameFormat::GrayU16 => write!(f, "GrayU16"),
+        }
+    }
+}
+
+/// The API that a [`CaptureBackendTrait`](crate::CaptureBackendTrait) implementation captures
+/// frames through.
+///
+/// `#[non_exhaustive]`: same caveat as [`FrameFormat`] - nokhwa-core's canonical `ApiBackend` has
+/// a variant per backend the full crate ships (V4L2, MSMF, AVFoundation, ...), none of which live
+/// in this tree, so this listing only covers the backends under `src/backends/capture` and must
+/// not be matched against as if it were the complete set.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ApiBackend {
+    /// Capture via `OpenCV`, e.g. [`NetworkCamera`](crate::backends::capture::NetworkCamera).
+    OpenCv,
+    /// Capture via `libcamera`, e.g.
+    /// [`LibCameraCaptureDevice`](crate::backends::capture::LibCameraCaptureDevice).
+    LibCamera,
+}
+
+impl Display for ApiBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiBackend::OpenCv => write!(f, "OpenCV"),
+            ApiBackend::LibCamera => write!(f, "LibCamera"),
+        }
+    }
+}